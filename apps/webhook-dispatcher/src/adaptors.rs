@@ -0,0 +1,96 @@
+use crate::models::{WebhookDestinationFormat, WebhookPayload};
+
+/// Transforms a [`WebhookPayload`] into the JSON body a destination
+/// actually expects. `Firecrawl` passes the payload through untouched;
+/// chat destinations get a short, human-readable summary instead, since
+/// they don't understand Firecrawl's native shape.
+pub fn adapt(format: WebhookDestinationFormat, payload: &WebhookPayload) -> serde_json::Value {
+    match format {
+        WebhookDestinationFormat::Firecrawl => {
+            serde_json::to_value(payload).unwrap_or(serde_json::Value::Null)
+        }
+        WebhookDestinationFormat::Slack => slack_message(payload),
+        WebhookDestinationFormat::Discord => discord_message(payload),
+    }
+}
+
+fn summarize(payload: &WebhookPayload) -> String {
+    let status = if payload.success { "succeeded" } else { "failed" };
+    let mut summary = format!(
+        "Firecrawl `{}` {status} ({} item{})",
+        payload.event_type,
+        payload.data.len(),
+        if payload.data.len() == 1 { "" } else { "s" }
+    );
+    if let Some(error) = &payload.error {
+        summary.push_str(&format!(" — {error}"));
+    }
+    summary
+}
+
+fn slack_message(payload: &WebhookPayload) -> serde_json::Value {
+    let text = summarize(payload);
+    serde_json::json!({
+        "text": text,
+        "blocks": [
+            {
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": text }
+            }
+        ]
+    })
+}
+
+fn discord_message(payload: &WebhookPayload) -> serde_json::Value {
+    serde_json::json!({ "content": summarize(payload) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_payload() -> WebhookPayload {
+        WebhookPayload {
+            success: true,
+            event_type: "crawl.completed".to_string(),
+            webhook_id: "wh_123".to_string(),
+            id: None,
+            job_id: None,
+            data: vec![serde_json::json!({"url": "https://example.com"})],
+            error: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn firecrawl_format_passes_the_payload_through() {
+        let payload = sample_payload();
+        let adapted = adapt(WebhookDestinationFormat::Firecrawl, &payload);
+        assert_eq!(adapted["type"], "crawl.completed");
+        assert_eq!(adapted["webhookId"], "wh_123");
+    }
+
+    #[test]
+    fn slack_format_builds_text_and_blocks() {
+        let adapted = adapt(WebhookDestinationFormat::Slack, &sample_payload());
+        assert!(adapted["text"].as_str().unwrap().contains("succeeded"));
+        assert_eq!(adapted["blocks"][0]["type"], "section");
+    }
+
+    #[test]
+    fn discord_format_builds_a_content_string() {
+        let adapted = adapt(WebhookDestinationFormat::Discord, &sample_payload());
+        let content = adapted["content"].as_str().unwrap();
+        assert!(content.contains("crawl.completed"));
+        assert!(content.contains("1 item"));
+    }
+
+    #[test]
+    fn discord_format_surfaces_the_error() {
+        let mut payload = sample_payload();
+        payload.success = false;
+        payload.error = Some("timed out".to_string());
+        let adapted = adapt(WebhookDestinationFormat::Discord, &payload);
+        assert!(adapted["content"].as_str().unwrap().contains("timed out"));
+    }
+}
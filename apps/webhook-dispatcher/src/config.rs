@@ -6,9 +6,15 @@ pub struct Config {
     pub rabbitmq_url: String,
     pub supabase_url: String,
     pub supabase_service_token: String,
+    pub redis_url: String,
     pub retry_delay_ms: u64,
+    pub retry_delay_cap_ms: u64,
     pub max_retries: u32,
     pub prefetch_count: u16,
+    pub webhook_dlq_name: String,
+    pub rate_limit_per_sec: f64,
+    pub rate_limit_burst: f64,
+    pub max_retry_after_ms: u64,
 }
 
 impl Config {
@@ -20,10 +26,15 @@ impl Config {
             supabase_url: env::var("SUPABASE_URL").context("SUPABASE_URL must be set")?,
             supabase_service_token: env::var("SUPABASE_SERVICE_TOKEN")
                 .context("SUPABASE_SERVICE_TOKEN must be set")?,
+            redis_url: env::var("REDIS_URL").context("REDIS_URL must be set")?,
             retry_delay_ms: env::var("WEBHOOK_RETRY_DELAY_MS")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(60000),
+            retry_delay_cap_ms: env::var("WEBHOOK_RETRY_DELAY_CAP_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3_600_000),
             max_retries: env::var("WEBHOOK_MAX_RETRIES")
                 .ok()
                 .and_then(|v| v.parse().ok())
@@ -32,6 +43,22 @@ impl Config {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(100),
+            webhook_dlq_name: env::var("WEBHOOK_DLQ_NAME")
+                .unwrap_or_else(|_| "webhook-dead-letter".to_string()),
+            rate_limit_per_sec: env::var("WEBHOOK_RATE_LIMIT_PER_SEC")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| v.is_finite() && *v > 0.0)
+                .unwrap_or(10.0),
+            rate_limit_burst: env::var("WEBHOOK_RATE_BURST")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| v.is_finite() && *v > 0.0)
+                .unwrap_or(20.0),
+            max_retry_after_ms: env::var("WEBHOOK_MAX_RETRY_AFTER_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(300_000),
         })
     }
 }
@@ -0,0 +1,174 @@
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::Client;
+use std::str::FromStr;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::adaptors;
+use crate::config::Config;
+use crate::logs;
+use crate::models::WebhookQueueMessage;
+use crate::signing::{self, SigningSecret};
+
+/// The outcome of a single delivery attempt, independent of whether it
+/// ultimately succeeded.
+pub struct DeliveryOutcome {
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    /// The requeue delay the endpoint asked for via a `Retry-After` header
+    /// on a 429 or 503, clamped to `max_retry_after_ms`. `None` when the
+    /// response didn't send one (or there was no response at all).
+    pub retry_after_ms: Option<u64>,
+    pub latency_ms: u64,
+}
+
+/// Parses a `Retry-After` header per RFC 7231: either delta-seconds or an
+/// HTTP-date, converted to milliseconds and clamped to `max_retry_after_ms`
+/// so a misbehaving endpoint can't park a message forever.
+fn parse_retry_after(value: &str, max_retry_after_ms: u64) -> Option<u64> {
+    let ms = if let Ok(delta_seconds) = value.trim().parse::<u64>() {
+        delta_seconds.saturating_mul(1000)
+    } else {
+        let date = httpdate::parse_http_date(value.trim()).ok()?;
+        let now = SystemTime::now();
+        date.duration_since(now).ok()?.as_millis() as u64
+    };
+
+    Some(ms.min(max_retry_after_ms))
+}
+
+/// Delivers one webhook attempt: serializes the payload, signs it with
+/// every active [`SigningSecret`] for the team (so a receiver can verify
+/// against either key mid-rotation), and POSTs it. Header precedence is
+/// explicit: the signature headers and `content-type` always win over a
+/// destination's custom `headers`, which only fill in names that don't
+/// collide.
+pub async fn deliver(
+    client: &Client,
+    message: &WebhookQueueMessage,
+    secrets: &[SigningSecret],
+    max_retry_after_ms: u64,
+) -> Result<DeliveryOutcome> {
+    let body = serde_json::to_string(&adaptors::adapt(message.format, &message.payload))
+        .context("failed to serialize payload")?;
+
+    let mut headers = HeaderMap::new();
+    for (name, value) in &message.headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::from_str(name), HeaderValue::from_str(value))
+        {
+            headers.insert(name, value);
+        }
+    }
+
+    if !secrets.is_empty() {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .context("system clock is before the UNIX epoch")?
+            .as_secs() as i64;
+        let signed = signing::sign_with_secrets(secrets, &message.payload.webhook_id, timestamp, &body);
+        for (name, value) in signed.into_map() {
+            headers.insert(
+                HeaderName::from_str(&name).expect("header name is a static literal"),
+                HeaderValue::from_str(&value).context("signature header value is not valid")?,
+            );
+        }
+    }
+
+    headers.insert(
+        reqwest::header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+
+    let started = Instant::now();
+    let response = client
+        .post(&message.webhook_url)
+        .timeout(Duration::from_millis(message.timeout_ms))
+        .headers(headers)
+        .body(body)
+        .send()
+        .await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    Ok(match response {
+        Ok(response) => {
+            let status = response.status();
+            let retry_after_ms = if status.as_u16() == 429 || status.as_u16() == 503 {
+                response
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| parse_retry_after(value, max_retry_after_ms))
+            } else {
+                None
+            };
+
+            DeliveryOutcome {
+                status_code: Some(status.as_u16() as i32),
+                error: None,
+                retry_after_ms,
+                latency_ms,
+            }
+        }
+        Err(err) => DeliveryOutcome {
+            status_code: err.status().map(|s| s.as_u16() as i32),
+            error: Some(err.to_string()),
+            retry_after_ms: None,
+            latency_ms,
+        },
+    })
+}
+
+/// Delivers one attempt and records it in `WebhookLog`, so the status code
+/// and any `Retry-After` hint an endpoint sent back are visible to operators
+/// as soon as the attempt happens, not just once a message is dead-lettered.
+pub async fn deliver_and_log(
+    client: &Client,
+    config: &Config,
+    message: &WebhookQueueMessage,
+    secrets: &[SigningSecret],
+) -> Result<DeliveryOutcome> {
+    let outcome = deliver(client, message, secrets, config.max_retry_after_ms).await?;
+
+    let log = logs::build(message, &outcome);
+    logs::record(client, config, &log)
+        .await
+        .context("failed to record webhook delivery in WebhookLog")?;
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_delta_seconds() {
+        assert_eq!(parse_retry_after("120", 300_000), Some(120_000));
+    }
+
+    #[test]
+    fn clamps_delta_seconds_to_the_max() {
+        assert_eq!(parse_retry_after("3600", 300_000), Some(300_000));
+    }
+
+    #[test]
+    fn parses_an_http_date_in_the_future() {
+        let future = SystemTime::now() + Duration::from_secs(60);
+        let header = httpdate::fmt_http_date(future);
+        let parsed = parse_retry_after(&header, 300_000).expect("should parse");
+        // allow a little slack for the time elapsed formatting/parsing the header
+        assert!((55_000..=60_000).contains(&parsed), "parsed = {parsed}");
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert_eq!(parse_retry_after("not a date or a number", 300_000), None);
+    }
+
+    #[test]
+    fn an_http_date_in_the_past_yields_no_delay() {
+        let past = SystemTime::now() - Duration::from_secs(60);
+        let header = httpdate::fmt_http_date(past);
+        assert_eq!(parse_retry_after(&header, 300_000), None);
+    }
+}
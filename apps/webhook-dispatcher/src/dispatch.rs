@@ -0,0 +1,79 @@
+use anyhow::{Context, Result};
+use lapin::Channel;
+use reqwest::Client;
+
+use crate::config::Config;
+use crate::delivery::{self, DeliveryOutcome};
+use crate::models::WebhookQueueMessage;
+use crate::queue;
+use crate::rate_limit;
+use crate::retry::{self, RetryDecision};
+use crate::signing::SigningSecret;
+
+/// Handles one message popped off the main delivery queue: rate-limits it
+/// per destination, delivers it, and on failure either requeues it with a
+/// jittered backoff delay or dead-letters it once retries are exhausted.
+/// This is the glue between `delivery`, `rate_limit`, `retry`, and `queue` —
+/// none of those modules call each other directly.
+///
+/// Returns `None` when the message was held back by the rate limiter
+/// instead of being delivered.
+pub async fn process(
+    channel: &Channel,
+    client: &Client,
+    config: &Config,
+    redis_conn: &mut redis::aio::MultiplexedConnection,
+    exchange: &str,
+    routing_key: &str,
+    message: &WebhookQueueMessage,
+    secrets: &[SigningSecret],
+) -> Result<Option<DeliveryOutcome>> {
+    let destination_key = rate_limit::destination_key(message);
+    let allowed = rate_limit::try_consume(
+        redis_conn,
+        &destination_key,
+        config.rate_limit_per_sec,
+        config.rate_limit_burst,
+    )
+    .await
+    .context("failed to check the per-destination rate limit")?;
+
+    if !allowed {
+        let delay_ms = rate_limit::requeue_delay_ms(config.rate_limit_per_sec);
+        queue::republish_with_delay(channel, exchange, routing_key, message, delay_ms)
+            .await
+            .context("failed to requeue rate-limited message")?;
+        return Ok(None);
+    }
+
+    let outcome = delivery::deliver_and_log(client, config, message, secrets).await?;
+    let succeeded = matches!(outcome.status_code, Some(code) if (200..300).contains(&code));
+
+    if !succeeded {
+        match retry::decide(message.retry_count, config) {
+            RetryDecision::Requeue(backoff_delay_ms) => {
+                let mut retried = message.clone();
+                retried.retry_count += 1;
+                // a server's own Retry-After hint takes precedence over our backoff guess
+                let delay_ms = outcome.retry_after_ms.unwrap_or(backoff_delay_ms);
+                queue::republish_with_delay(channel, exchange, routing_key, &retried, delay_ms)
+                    .await
+                    .context("failed to requeue message for retry")?;
+            }
+            RetryDecision::DeadLetter => {
+                queue::publish_dead_letter(
+                    channel,
+                    client,
+                    config,
+                    &config.webhook_dlq_name,
+                    message,
+                    &outcome,
+                )
+                .await
+                .context("failed to dead-letter message")?;
+            }
+        }
+    }
+
+    Ok(Some(outcome))
+}
@@ -0,0 +1,12 @@
+pub mod adaptors;
+pub mod config;
+pub mod delivery;
+pub mod dispatch;
+pub mod logs;
+pub mod models;
+pub mod ping;
+pub mod queue;
+pub mod rate_limit;
+pub mod retry;
+pub mod secrets;
+pub mod signing;
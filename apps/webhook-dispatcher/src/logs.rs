@@ -0,0 +1,46 @@
+use anyhow::{Context, Result};
+use reqwest::Client;
+
+use crate::config::Config;
+use crate::delivery::DeliveryOutcome;
+use crate::models::{WebhookLog, WebhookQueueMessage};
+
+/// Builds the [`WebhookLog`] row for one delivery attempt against `message`.
+pub fn build(message: &WebhookQueueMessage, outcome: &DeliveryOutcome) -> WebhookLog {
+    let success = matches!(outcome.status_code, Some(code) if (200..300).contains(&code));
+
+    WebhookLog {
+        success,
+        error: outcome.error.clone(),
+        team_id: message.team_id.clone(),
+        crawl_id: message.job_id.clone(),
+        scrape_id: message.scrape_id.clone(),
+        url: message.webhook_url.clone(),
+        status_code: outcome.status_code,
+        event: message.event.clone(),
+        retry_count: message.retry_count,
+        retry_after_ms: outcome.retry_after_ms,
+        latency_ms: outcome.latency_ms,
+    }
+}
+
+/// Persists a delivery attempt's outcome to Supabase so operators can see
+/// retries, Retry-After hints, dead-letters, and ping results without
+/// tailing dispatcher logs.
+pub async fn record(client: &Client, config: &Config, log: &WebhookLog) -> Result<()> {
+    client
+        .post(format!("{}/rest/v1/webhook_logs", config.supabase_url))
+        .header("apikey", &config.supabase_service_token)
+        .header(
+            "Authorization",
+            format!("Bearer {}", config.supabase_service_token),
+        )
+        .json(log)
+        .send()
+        .await
+        .context("failed to record webhook log")?
+        .error_for_status()
+        .context("supabase returned an error recording webhook log")?;
+
+    Ok(())
+}
@@ -13,6 +13,21 @@ pub struct WebhookQueueMessage {
     pub timeout_ms: u64,
     #[serde(default)]
     pub retry_count: u32,
+    #[serde(default)]
+    pub format: WebhookDestinationFormat,
+}
+
+/// The envelope a destination expects. `Firecrawl` is the native shape
+/// ([`WebhookPayload`] serialized as-is); the others adapt it to a chat
+/// incoming-webhook so users can point a crawl webhook straight at Slack
+/// or Discord without a middleman service.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookDestinationFormat {
+    #[default]
+    Firecrawl,
+    Slack,
+    Discord,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -43,4 +58,7 @@ pub struct WebhookLog {
     pub url: String,
     pub status_code: Option<i32>,
     pub event: String,
+    pub retry_count: u32,
+    pub retry_after_ms: Option<u64>,
+    pub latency_ms: u64,
 }
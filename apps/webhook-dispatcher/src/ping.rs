@@ -0,0 +1,113 @@
+use anyhow::Result;
+use reqwest::Client;
+
+use crate::config::Config;
+use crate::delivery::{self, DeliveryOutcome};
+use crate::models::WebhookQueueMessage;
+use crate::signing::SigningSecret;
+
+pub const PING_EVENT: &str = "webhook.test";
+pub const PING_EVENT_TYPE: &str = "ping";
+
+/// Turns a regular queue message into a ping: same URL, headers, and
+/// signing as a real delivery, but an empty `data` array and the `ping`
+/// event type, so users can confirm a webhook is reachable before a real
+/// crawl fires.
+pub fn ping_message(mut message: WebhookQueueMessage) -> WebhookQueueMessage {
+    message.event = PING_EVENT.to_string();
+    message.payload.event_type = PING_EVENT_TYPE.to_string();
+    message.payload.data = Vec::new();
+    message.payload.error = None;
+    message.payload.success = true;
+    message
+}
+
+/// The result of a single ping delivery, shaped for the API layer to hand
+/// straight back to the user: did the endpoint accept it, and how long did
+/// it take.
+pub struct PingOutcome {
+    pub webhook_url: String,
+    pub accepted: bool,
+    pub latency_ms: u64,
+    pub delivery: DeliveryOutcome,
+}
+
+/// Delivers a ping and reports the full round trip, mirroring the "test
+/// delivery" button pattern common in Git forge webhook UIs. The attempt is
+/// recorded in `WebhookLog` just like a real delivery, so a ping shows up
+/// in the same place operators already look for delivery history.
+pub async fn deliver_ping(
+    client: &Client,
+    config: &Config,
+    message: &WebhookQueueMessage,
+    secrets: &[SigningSecret],
+) -> Result<PingOutcome> {
+    let delivery = delivery::deliver_and_log(client, config, message, secrets).await?;
+    let accepted = is_accepted(delivery.status_code);
+
+    Ok(PingOutcome {
+        webhook_url: message.webhook_url.clone(),
+        accepted,
+        latency_ms: delivery.latency_ms,
+        delivery,
+    })
+}
+
+/// Whether a ping's response counts as the endpoint accepting it.
+fn is_accepted(status_code: Option<i32>) -> bool {
+    matches!(status_code, Some(code) if (200..300).contains(&code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{WebhookDestinationFormat, WebhookPayload};
+    use std::collections::HashMap;
+
+    fn sample_message() -> WebhookQueueMessage {
+        WebhookQueueMessage {
+            webhook_url: "https://example.com/hook".to_string(),
+            payload: WebhookPayload {
+                success: false,
+                event_type: "crawl.completed".to_string(),
+                webhook_id: "wh_123".to_string(),
+                id: None,
+                job_id: None,
+                data: vec![serde_json::json!({"url": "https://example.com"})],
+                error: Some("boom".to_string()),
+                metadata: None,
+            },
+            headers: HashMap::new(),
+            team_id: "team_1".to_string(),
+            job_id: "job_1".to_string(),
+            scrape_id: None,
+            event: "crawl.completed".to_string(),
+            timeout_ms: 5000,
+            retry_count: 0,
+            format: WebhookDestinationFormat::Firecrawl,
+        }
+    }
+
+    #[test]
+    fn ping_message_clears_data_and_marks_it_a_ping() {
+        let ping = ping_message(sample_message());
+
+        assert_eq!(ping.event, PING_EVENT);
+        assert_eq!(ping.payload.event_type, PING_EVENT_TYPE);
+        assert!(ping.payload.data.is_empty());
+        assert!(ping.payload.success);
+        assert!(ping.payload.error.is_none());
+        // everything else about the destination is left alone
+        assert_eq!(ping.webhook_url, "https://example.com/hook");
+        assert_eq!(ping.team_id, "team_1");
+    }
+
+    #[test]
+    fn is_accepted_treats_only_2xx_as_success() {
+        assert!(is_accepted(Some(200)));
+        assert!(is_accepted(Some(299)));
+        assert!(!is_accepted(Some(404)));
+        assert!(!is_accepted(Some(500)));
+        assert!(!is_accepted(None));
+    }
+}
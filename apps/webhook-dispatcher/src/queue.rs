@@ -0,0 +1,93 @@
+use anyhow::{Context, Result};
+use lapin::{options::BasicPublishOptions, types::FieldTable, BasicProperties, Channel};
+use reqwest::Client;
+
+use crate::config::Config;
+use crate::delivery::DeliveryOutcome;
+use crate::logs;
+use crate::models::WebhookQueueMessage;
+
+/// Republishes a message for a later retry attempt. The delay is carried as
+/// a per-message TTL on the given queue/exchange, so the broker (not this
+/// process) is what holds the message until it's due; the consumer side is
+/// expected to route expired messages back into the main delivery queue.
+pub async fn republish_with_delay(
+    channel: &Channel,
+    exchange: &str,
+    routing_key: &str,
+    message: &WebhookQueueMessage,
+    delay_ms: u64,
+) -> Result<()> {
+    let body = serde_json::to_vec(message).context("failed to serialize retry message")?;
+    let properties = BasicProperties::default()
+        .with_expiration(delay_ms.to_string().into())
+        .with_delivery_mode(2);
+
+    channel
+        .basic_publish(
+            exchange,
+            routing_key,
+            BasicPublishOptions::default(),
+            &body,
+            properties,
+        )
+        .await
+        .context("failed to republish webhook message for retry")?
+        .await
+        .context("broker did not confirm retry republish")?;
+
+    Ok(())
+}
+
+/// Publishes a message that has exhausted its retries to the dead-letter
+/// queue instead of dropping it, so operators can inspect or replay it, and
+/// records the terminal failure (including the last delivery attempt's
+/// status/error) in `WebhookLog`.
+pub async fn publish_dead_letter(
+    channel: &Channel,
+    client: &Client,
+    config: &Config,
+    dlq_name: &str,
+    message: &WebhookQueueMessage,
+    last_outcome: &DeliveryOutcome,
+) -> Result<()> {
+    let body = serde_json::to_vec(message).context("failed to serialize dead-lettered message")?;
+
+    channel
+        .basic_publish(
+            "",
+            dlq_name,
+            BasicPublishOptions::default(),
+            &body,
+            BasicProperties::default().with_delivery_mode(2),
+        )
+        .await
+        .context("failed to publish message to dead-letter queue")?
+        .await
+        .context("broker did not confirm dead-letter publish")?;
+
+    let log = logs::build(message, last_outcome);
+    logs::record(client, config, &log)
+        .await
+        .context("failed to record dead-lettered webhook in WebhookLog")?;
+
+    Ok(())
+}
+
+/// Declares the dead-letter queue so `publish_dead_letter` always has
+/// somewhere to land, even on a fresh broker.
+pub async fn declare_dead_letter_queue(channel: &Channel, dlq_name: &str) -> Result<()> {
+    channel
+        .queue_declare(
+            dlq_name,
+            lapin::options::QueueDeclareOptions {
+                durable: true,
+                ..Default::default()
+            },
+            FieldTable::default(),
+        )
+        .await
+        .context("failed to declare dead-letter queue")?;
+
+    Ok(())
+}
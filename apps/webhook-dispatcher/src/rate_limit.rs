@@ -0,0 +1,111 @@
+use anyhow::{Context, Result};
+
+use crate::models::WebhookQueueMessage;
+
+/// Atomically refills and consumes one token from the bucket for `key`,
+/// storing `{tokens, last_refill_ts}` as a Redis hash. Refill and consume
+/// happen in a single Lua script so concurrent dispatcher instances can't
+/// race each other into over-spending the bucket.
+///
+/// The script returns `1` if a token was available and consumed, `0` if
+/// the bucket is empty.
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local key = KEYS[1]
+local rate = tonumber(ARGV[1])
+local burst = tonumber(ARGV[2])
+local now = tonumber(ARGV[3])
+
+local bucket = redis.call("HMGET", key, "tokens", "last_refill_ts")
+local tokens = tonumber(bucket[1])
+local last_refill_ts = tonumber(bucket[2])
+
+if tokens == nil then
+    tokens = burst
+    last_refill_ts = now
+end
+
+local elapsed = math.max(0, now - last_refill_ts)
+tokens = math.min(burst, tokens + elapsed * rate)
+
+local allowed = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+end
+
+redis.call("HSET", key, "tokens", tokens, "last_refill_ts", now)
+redis.call("EXPIRE", key, math.ceil(burst / rate) + 1)
+
+return allowed
+"#;
+
+/// Keys the token bucket by destination (host or `team_id`) so one noisy
+/// endpoint can't starve delivery concurrency for everyone else.
+pub async fn try_consume(
+    conn: &mut redis::aio::MultiplexedConnection,
+    destination_key: &str,
+    rate_per_sec: f64,
+    burst: f64,
+) -> Result<bool> {
+    let now = now_secs();
+    let key = format!("webhook-rate-limit:{destination_key}");
+
+    let allowed: i32 = redis::Script::new(TOKEN_BUCKET_SCRIPT)
+        .key(key)
+        .arg(rate_per_sec)
+        .arg(burst)
+        .arg(now)
+        .invoke_async(conn)
+        .await
+        .context("failed to evaluate token bucket script")?;
+
+    Ok(allowed == 1)
+}
+
+fn now_secs() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("system clock is before the UNIX epoch")
+        .as_secs_f64()
+}
+
+/// Computes how long to hold a message that was denied a token, so the
+/// consumer can requeue it instead of blocking on the limiter in place.
+/// `rate_per_sec` is expected to be a validated, positive `Config::rate_limit_per_sec`
+/// (see `Config::from_env`); a non-positive or non-finite rate falls back to
+/// one second rather than producing an absurd or saturating delay.
+pub fn requeue_delay_ms(rate_per_sec: f64) -> u64 {
+    if !rate_per_sec.is_finite() || rate_per_sec <= 0.0 {
+        return 1000;
+    }
+    ((1.0 / rate_per_sec) * 1000.0).ceil().max(1.0) as u64
+}
+
+/// Keys the token bucket by destination host, falling back to `team_id`
+/// when the webhook URL doesn't parse, so one noisy endpoint can't starve
+/// delivery concurrency for everyone else.
+pub fn destination_key(message: &WebhookQueueMessage) -> String {
+    reqwest::Url::parse(&message.webhook_url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| message.team_id.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requeue_delay_is_the_inverse_of_the_rate() {
+        assert_eq!(requeue_delay_ms(10.0), 100);
+        assert_eq!(requeue_delay_ms(1.0), 1000);
+    }
+
+    #[test]
+    fn requeue_delay_falls_back_for_non_positive_rates() {
+        assert_eq!(requeue_delay_ms(0.0), 1000);
+        assert_eq!(requeue_delay_ms(-5.0), 1000);
+        assert_eq!(requeue_delay_ms(f64::NAN), 1000);
+        assert_eq!(requeue_delay_ms(f64::INFINITY), 1000);
+    }
+}
@@ -0,0 +1,94 @@
+use rand::Rng;
+
+use crate::config::Config;
+
+/// What to do with a message after a failed delivery attempt.
+#[derive(Debug, PartialEq)]
+pub enum RetryDecision {
+    /// Requeue with this jittered backoff delay, in milliseconds.
+    Requeue(u64),
+    /// Retries are exhausted; send to the dead-letter queue instead.
+    DeadLetter,
+}
+
+/// Decides whether a failed message should be requeued with a jittered
+/// backoff delay or dead-lettered, based on `retry_count` vs `config.max_retries`.
+pub fn decide(retry_count: u32, config: &Config) -> RetryDecision {
+    if retry_count > config.max_retries {
+        RetryDecision::DeadLetter
+    } else {
+        RetryDecision::Requeue(compute_backoff_delay(
+            config.retry_delay_ms,
+            retry_count,
+            config.retry_delay_cap_ms,
+        ))
+    }
+}
+
+/// Computes the delay before the next delivery attempt using exponential
+/// backoff with full jitter: `min(retry_delay_ms * 2^retry_count, cap)`,
+/// then a uniform draw over `[0, that]`. Full jitter (rather than a fixed
+/// or half-jittered delay) avoids a thundering herd of retries all landing
+/// on the same cadence against a flaky endpoint.
+pub fn compute_backoff_delay(retry_delay_ms: u64, retry_count: u32, cap_ms: u64) -> u64 {
+    let exponential = retry_delay_ms.saturating_mul(1u64 << retry_count.min(62));
+    let bounded = exponential.min(cap_ms);
+    if bounded == 0 {
+        return 0;
+    }
+    rand::thread_rng().gen_range(0..=bounded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config(max_retries: u32) -> Config {
+        Config {
+            rabbitmq_url: String::new(),
+            supabase_url: String::new(),
+            supabase_service_token: String::new(),
+            redis_url: String::new(),
+            retry_delay_ms: 1000,
+            retry_delay_cap_ms: 60_000,
+            max_retries,
+            prefetch_count: 100,
+            webhook_dlq_name: "webhook-dead-letter".to_string(),
+            rate_limit_per_sec: 10.0,
+            rate_limit_burst: 20.0,
+            max_retry_after_ms: 300_000,
+        }
+    }
+
+    #[test]
+    fn decide_requeues_while_under_the_retry_limit() {
+        let config = test_config(3);
+        assert!(matches!(decide(0, &config), RetryDecision::Requeue(_)));
+        assert!(matches!(decide(3, &config), RetryDecision::Requeue(_)));
+    }
+
+    #[test]
+    fn decide_dead_letters_once_retries_are_exhausted() {
+        let config = test_config(3);
+        assert_eq!(decide(4, &config), RetryDecision::DeadLetter);
+    }
+
+    #[test]
+    fn delay_is_bounded_by_the_cap() {
+        for retry_count in 0..10 {
+            let delay = compute_backoff_delay(1000, retry_count, 5000);
+            assert!(delay <= 5000, "delay {delay} exceeded cap at retry {retry_count}");
+        }
+    }
+
+    #[test]
+    fn zero_base_delay_never_waits() {
+        assert_eq!(compute_backoff_delay(0, 3, 5000), 0);
+    }
+
+    #[test]
+    fn large_retry_count_does_not_overflow() {
+        let delay = compute_backoff_delay(1000, u32::MAX, 60_000);
+        assert!(delay <= 60_000);
+    }
+}
@@ -0,0 +1,49 @@
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::config::Config;
+use crate::signing::SigningSecret;
+
+#[derive(Debug, Deserialize)]
+struct SigningSecretRow {
+    secret: String,
+}
+
+/// Looks up every active webhook signing secret configured for a team.
+///
+/// Secrets live in Supabase rather than `Config` since they're per-team.
+/// A team can have more than one active row while rotating keys — callers
+/// are expected to sign with all of them (see [`crate::signing::sign_with_secrets`])
+/// rather than pick an arbitrary one.
+pub async fn fetch_signing_secrets(
+    client: &Client,
+    config: &Config,
+    team_id: &str,
+) -> Result<Vec<SigningSecret>> {
+    let url = format!(
+        "{}/rest/v1/webhook_signing_secrets?team_id=eq.{}&active=eq.true&select=secret&order=created_at.asc",
+        config.supabase_url,
+        urlencoding::encode(team_id),
+    );
+
+    let rows: Vec<SigningSecretRow> = client
+        .get(&url)
+        .header("apikey", &config.supabase_service_token)
+        .header(
+            "Authorization",
+            format!("Bearer {}", config.supabase_service_token),
+        )
+        .send()
+        .await
+        .context("failed to query webhook signing secrets")?
+        .error_for_status()
+        .context("supabase returned an error fetching webhook signing secrets")?
+        .json()
+        .await
+        .context("failed to parse webhook signing secrets response")?;
+
+    rows.into_iter()
+        .map(|row| SigningSecret::from_encoded(&row.secret))
+        .collect()
+}